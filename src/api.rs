@@ -0,0 +1,165 @@
+// HTTP API exposing the live order book and derived quote metrics, mirroring
+// the `/orderbook` and `/candles` style routes of openbook-candles.
+//
+// Handlers read from a shared, lock-protected copy of the `OrderBook` that
+// the websocket task keeps current, so external consumers can poll state
+// without opening their own exchange connection.
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::get,
+    Json, Router,
+};
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::orderbook::{
+    calculate_weighted_average_price_asks, calculate_weighted_average_price_bids, OrderBook,
+};
+use crate::quote::Rate;
+
+/// State shared between the websocket task that keeps the book current and
+/// the HTTP handlers that serve it.
+#[derive(Clone)]
+pub struct AppState {
+    pub symbol: String,
+    pub ask_spread: Decimal,
+    pub book: Arc<RwLock<OrderBook>>,
+}
+
+pub fn router(state: AppState) -> Router {
+    Router::new()
+        .route("/orderbook", get(get_orderbook))
+        .route("/quote", get(get_quote))
+        .with_state(state)
+}
+
+#[derive(Deserialize)]
+struct OrderBookParams {
+    symbol: String,
+    depth: usize,
+}
+
+#[derive(Serialize)]
+struct Level {
+    price: f64,
+    quantity: f64,
+}
+
+#[derive(Serialize)]
+struct OrderBookResponse {
+    symbol: String,
+    bids: Vec<Level>,
+    asks: Vec<Level>,
+}
+
+/// `GET /orderbook?symbol=...&depth=N` — the top `N` aggregated bid/ask
+/// levels, best price first on each side.
+async fn get_orderbook(
+    State(state): State<AppState>,
+    Query(params): Query<OrderBookParams>,
+) -> impl IntoResponse {
+    if !params.symbol.eq_ignore_ascii_case(&state.symbol) {
+        return (StatusCode::NOT_FOUND, Json(ErrorResponse::unknown_symbol())).into_response();
+    }
+
+    let book = state.book.read().await;
+    let bids = book
+        .bids
+        .iter()
+        .rev()
+        .take(params.depth)
+        .map(|(price, quantity)| Level {
+            price: price.to_f64().unwrap_or(0.0),
+            quantity: quantity.to_f64().unwrap_or(0.0),
+        })
+        .collect();
+    let asks = book
+        .asks
+        .iter()
+        .take(params.depth)
+        .map(|(price, quantity)| Level {
+            price: price.to_f64().unwrap_or(0.0),
+            quantity: quantity.to_f64().unwrap_or(0.0),
+        })
+        .collect();
+
+    Json(OrderBookResponse {
+        symbol: state.symbol.clone(),
+        bids,
+        asks,
+    })
+    .into_response()
+}
+
+#[derive(Deserialize)]
+struct QuoteParams {
+    symbol: String,
+    depth: f64,
+}
+
+#[derive(Serialize)]
+struct QuoteResponse {
+    symbol: String,
+    bid: f64,
+    ask: f64,
+    ask_raw: f64,
+    spread: f64,
+}
+
+/// `GET /quote?symbol=...&depth=D` — the depth-weighted bid/ask and spread
+/// for `D` units of notional, with the configured ask-spread applied to the
+/// ask side.
+async fn get_quote(
+    State(state): State<AppState>,
+    Query(params): Query<QuoteParams>,
+) -> impl IntoResponse {
+    if !params.symbol.eq_ignore_ascii_case(&state.symbol) {
+        return (StatusCode::NOT_FOUND, Json(ErrorResponse::unknown_symbol())).into_response();
+    }
+    let Some(depth) = Decimal::from_f64(params.depth) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::invalid_depth()),
+        )
+            .into_response();
+    };
+
+    let book = state.book.read().await;
+    let bid = calculate_weighted_average_price_bids(&book.bids, depth).unwrap_or(Decimal::ZERO);
+    let ask_raw = calculate_weighted_average_price_asks(&book.asks, depth).unwrap_or(Decimal::ZERO);
+    let ask = Rate::new(ask_raw, state.ask_spread).quoted();
+
+    Json(QuoteResponse {
+        symbol: state.symbol.clone(),
+        bid: bid.to_f64().unwrap_or(0.0),
+        ask: ask.to_f64().unwrap_or(0.0),
+        ask_raw: ask_raw.to_f64().unwrap_or(0.0),
+        spread: (ask - bid).to_f64().unwrap_or(0.0),
+    })
+    .into_response()
+}
+
+#[derive(Serialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+impl ErrorResponse {
+    fn unknown_symbol() -> Self {
+        ErrorResponse {
+            error: "unknown symbol".to_string(),
+        }
+    }
+
+    fn invalid_depth() -> Self {
+        ErrorResponse {
+            error: "invalid depth".to_string(),
+        }
+    }
+}
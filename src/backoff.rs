@@ -0,0 +1,89 @@
+// Bounded exponential backoff with jitter for reconnect loops.
+//
+// Each retry doubles the delay up to `max`, with random jitter layered on
+// top so a fleet of watchers reconnecting after a shared outage doesn't
+// thunder back in lockstep.
+
+use rand::Rng;
+use std::time::Duration;
+
+pub struct Backoff {
+    base: Duration,
+    max: Duration,
+    attempt: u32,
+}
+
+impl Backoff {
+    pub fn new(base: Duration, max: Duration) -> Self {
+        Backoff {
+            base,
+            max,
+            attempt: 0,
+        }
+    }
+
+    /// The delay before the next retry, doubling each call up to `max` and
+    /// jittered by up to 50% so retries don't stay in lockstep.
+    pub fn next_delay(&mut self) -> Duration {
+        let exp = self.base.saturating_mul(1u32 << self.attempt.min(16));
+        let capped = exp.min(self.max);
+        self.attempt += 1;
+
+        let jitter_ms = rand::thread_rng().gen_range(0..=(capped.as_millis() as u64 / 2).max(1));
+        capped + Duration::from_millis(jitter_ms)
+    }
+
+    /// Reset to the initial delay after a successful connection.
+    pub fn reset(&mut self) {
+        self.attempt = 0;
+    }
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Backoff::new(Duration::from_millis(500), Duration::from_secs(30))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delay_doubles_each_attempt_up_to_the_cap() {
+        let base = Duration::from_millis(100);
+        let max = Duration::from_secs(10);
+        let mut backoff = Backoff::new(base, max);
+
+        // Jitter adds up to 50% on top of the doubled delay, so check the
+        // floor rather than an exact value.
+        assert!(backoff.next_delay() >= base);
+        assert!(backoff.next_delay() >= base * 2);
+        assert!(backoff.next_delay() >= base * 4);
+    }
+
+    #[test]
+    fn delay_never_exceeds_max_plus_jitter() {
+        let base = Duration::from_millis(100);
+        let max = Duration::from_millis(500);
+        let mut backoff = Backoff::new(base, max);
+
+        for _ in 0..20 {
+            let delay = backoff.next_delay();
+            assert!(delay <= max + max / 2);
+        }
+    }
+
+    #[test]
+    fn reset_restarts_from_the_base_delay() {
+        let base = Duration::from_millis(100);
+        let max = Duration::from_secs(10);
+        let mut backoff = Backoff::new(base, max);
+
+        backoff.next_delay();
+        backoff.next_delay();
+        backoff.reset();
+
+        assert!(backoff.next_delay() < base * 2);
+    }
+}
@@ -0,0 +1,126 @@
+// Binance `@depth` diff stream, plus its REST depth-snapshot endpoint.
+
+use async_trait::async_trait;
+use futures_util::{SinkExt, StreamExt};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::str::FromStr;
+use tokio_tungstenite::{connect_async, tungstenite::protocol::Message};
+use url::Url;
+
+use super::{BookUpdate, ExchangeError, Order, OrderBookSource, WsStream};
+use crate::orderbook::DepthSnapshot;
+
+pub struct BinanceSource {
+    symbol: String,
+    socket: Option<WsStream>,
+}
+
+impl BinanceSource {
+    pub fn new(symbol: impl Into<String>) -> Self {
+        BinanceSource {
+            symbol: symbol.into(),
+            socket: None,
+        }
+    }
+}
+
+#[async_trait]
+impl OrderBookSource for BinanceSource {
+    async fn connect(&mut self) -> Result<(), ExchangeError> {
+        let url = Url::parse(&format!(
+            "wss://stream.binance.com:9443/ws/{}@depth",
+            self.symbol
+        ))
+        .map_err(|e| ExchangeError::Transport(e.to_string()))?;
+
+        let (socket, _response) = connect_async(url)
+            .await
+            .map_err(|e| ExchangeError::Transport(e.to_string()))?;
+        self.socket = Some(socket);
+        Ok(())
+    }
+
+    async fn snapshot(&self) -> Result<DepthSnapshot, ExchangeError> {
+        let url = format!(
+            "https://api.binance.com/api/v3/depth?symbol={}&limit=1000",
+            self.symbol.to_uppercase()
+        );
+        let snapshot: RestDepthSnapshot = reqwest::get(&url)
+            .await
+            .map_err(|e| ExchangeError::Transport(e.to_string()))?
+            .json()
+            .await
+            .map_err(|e| ExchangeError::Decode(e.to_string()))?;
+
+        Ok(DepthSnapshot {
+            last_update_id: snapshot.last_update_id,
+            bids: parse_levels(&snapshot.bids),
+            asks: parse_levels(&snapshot.asks),
+        })
+    }
+
+    async fn next_update(&mut self) -> Result<Option<BookUpdate>, ExchangeError> {
+        let socket = self
+            .socket
+            .as_mut()
+            .expect("BinanceSource::connect must be called before next_update");
+
+        loop {
+            let Some(message) = socket.next().await else {
+                return Ok(None);
+            };
+            let message = message.map_err(|e| ExchangeError::Transport(e.to_string()))?;
+            let text = match message {
+                Message::Text(text) => text,
+                Message::Ping(payload) => {
+                    socket
+                        .send(Message::Pong(payload))
+                        .await
+                        .map_err(|e| ExchangeError::Transport(e.to_string()))?;
+                    continue;
+                }
+                _ => continue,
+            };
+            let update: DepthUpdate =
+                serde_json::from_str(&text).map_err(|e| ExchangeError::Decode(e.to_string()))?;
+
+            return Ok(Some(BookUpdate {
+                bids: parse_levels(&update.b),
+                asks: parse_levels(&update.a),
+                first_update_id: Some(update.first_update_id),
+                final_update_id: Some(update.final_update_id),
+            }));
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct DepthUpdate {
+    #[serde(rename = "U")]
+    first_update_id: u64,
+    #[serde(rename = "u")]
+    final_update_id: u64,
+    b: Vec<[String; 2]>,
+    a: Vec<[String; 2]>,
+}
+
+#[derive(Deserialize, Debug)]
+struct RestDepthSnapshot {
+    #[serde(rename = "lastUpdateId")]
+    last_update_id: u64,
+    bids: Vec<[String; 2]>,
+    asks: Vec<[String; 2]>,
+}
+
+fn parse_levels(levels: &[[String; 2]]) -> Vec<Order> {
+    levels
+        .iter()
+        .filter_map(|[price, quantity]| {
+            Some(Order {
+                price: Decimal::from_str(price).ok()?,
+                quantity: Decimal::from_str(quantity).ok()?,
+            })
+        })
+        .collect()
+}
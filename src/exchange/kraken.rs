@@ -0,0 +1,184 @@
+// Kraken book websocket stream, plus its REST order-book endpoint.
+//
+// Update frames are untagged arrays: `[channelID, {"a": [...], "b": [...]},
+// channelName, pair]`, with `as`/`bs` used instead of `a`/`b` on the initial
+// snapshot frame. We only care about the book object(s) between the channel
+// ID and the channel name.
+
+use async_trait::async_trait;
+use futures_util::{SinkExt, StreamExt};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::str::FromStr;
+use tokio_tungstenite::{connect_async, tungstenite::protocol::Message};
+use url::Url;
+
+use super::{BookUpdate, ExchangeError, Order, OrderBookSource, WsStream};
+use crate::orderbook::DepthSnapshot;
+
+const WS_URL: &str = "wss://ws.kraken.com";
+
+pub struct KrakenSource {
+    pair: String,
+    socket: Option<WsStream>,
+}
+
+impl KrakenSource {
+    pub fn new(pair: impl Into<String>) -> Self {
+        KrakenSource {
+            pair: pair.into(),
+            socket: None,
+        }
+    }
+}
+
+#[async_trait]
+impl OrderBookSource for KrakenSource {
+    async fn connect(&mut self) -> Result<(), ExchangeError> {
+        let url = Url::parse(WS_URL).map_err(|e| ExchangeError::Transport(e.to_string()))?;
+        let (mut socket, _response) = connect_async(url)
+            .await
+            .map_err(|e| ExchangeError::Transport(e.to_string()))?;
+
+        let subscribe = json!({
+            "event": "subscribe",
+            "pair": [self.pair],
+            "subscription": {"name": "book"},
+        });
+        socket
+            .send(Message::Text(subscribe.to_string()))
+            .await
+            .map_err(|e| ExchangeError::Transport(e.to_string()))?;
+
+        self.socket = Some(socket);
+        Ok(())
+    }
+
+    async fn snapshot(&self) -> Result<DepthSnapshot, ExchangeError> {
+        let url = format!(
+            "https://api.kraken.com/0/public/Depth?pair={}&count=500",
+            self.pair.replace('/', "")
+        );
+        let response: RestResponse = reqwest::get(&url)
+            .await
+            .map_err(|e| ExchangeError::Transport(e.to_string()))?
+            .json()
+            .await
+            .map_err(|e| ExchangeError::Decode(e.to_string()))?;
+        let book = response
+            .result
+            .into_values()
+            .next()
+            .ok_or_else(|| ExchangeError::Decode("empty Kraken depth response".to_string()))?;
+
+        Ok(DepthSnapshot {
+            last_update_id: 0,
+            bids: parse_levels(&book.bids),
+            asks: parse_levels(&book.asks),
+        })
+    }
+
+    async fn next_update(&mut self) -> Result<Option<BookUpdate>, ExchangeError> {
+        let socket = self
+            .socket
+            .as_mut()
+            .expect("KrakenSource::connect must be called before next_update");
+
+        loop {
+            let Some(message) = socket.next().await else {
+                return Ok(None);
+            };
+            let message = message.map_err(|e| ExchangeError::Transport(e.to_string()))?;
+            let text = match message {
+                Message::Text(text) => text,
+                Message::Ping(payload) => {
+                    socket
+                        .send(Message::Pong(payload))
+                        .await
+                        .map_err(|e| ExchangeError::Transport(e.to_string()))?;
+                    continue;
+                }
+                _ => continue,
+            };
+            // Subscription acks/heartbeats arrive as JSON objects; only diff
+            // and snapshot frames are untagged arrays. A tick that updates
+            // both sides arrives as two separate book objects in the same
+            // frame (`[channelID, {"a":[...]}, {"b":[...]}, name, pair]`),
+            // so every element must be parsed and merged, not just the
+            // first one that matches.
+            let Ok(Value::Array(frame)) = serde_json::from_str::<Value>(&text) else {
+                continue;
+            };
+            let updates: Vec<LevelUpdate> = frame
+                .iter()
+                .filter_map(|element| serde_json::from_value::<LevelUpdate>(element.clone()).ok())
+                .collect();
+            if updates.is_empty() {
+                continue;
+            }
+
+            let bids: Vec<Vec<String>> = updates.iter().flat_map(LevelUpdate::bids).collect();
+            let asks: Vec<Vec<String>> = updates.iter().flat_map(LevelUpdate::asks).collect();
+
+            return Ok(Some(BookUpdate {
+                bids: parse_levels(&bids),
+                asks: parse_levels(&asks),
+                first_update_id: None,
+                final_update_id: None,
+            }));
+        }
+    }
+}
+
+// Levels are `[price, volume, timestamp]`, except "republished" levels
+// (marked with a trailing `"r"` flag) which carry a 4th element. A fixed-size
+// array fails to deserialize the longer form and drops the whole update, so
+// levels are kept as `Vec<String>` and only the first two fields are used.
+#[derive(Deserialize, Debug, Default)]
+struct LevelUpdate {
+    #[serde(default, rename = "a")]
+    ask_updates: Vec<Vec<String>>,
+    #[serde(default, rename = "b")]
+    bid_updates: Vec<Vec<String>>,
+    #[serde(default, rename = "as")]
+    ask_snapshot: Vec<Vec<String>>,
+    #[serde(default, rename = "bs")]
+    bid_snapshot: Vec<Vec<String>>,
+}
+
+impl LevelUpdate {
+    fn asks(&self) -> Vec<Vec<String>> {
+        [self.ask_updates.clone(), self.ask_snapshot.clone()].concat()
+    }
+
+    fn bids(&self) -> Vec<Vec<String>> {
+        [self.bid_updates.clone(), self.bid_snapshot.clone()].concat()
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct RestResponse {
+    result: HashMap<String, RestBookData>,
+}
+
+#[derive(Deserialize, Debug)]
+struct RestBookData {
+    asks: Vec<Vec<String>>,
+    bids: Vec<Vec<String>>,
+}
+
+fn parse_levels(levels: &[Vec<String>]) -> Vec<Order> {
+    levels
+        .iter()
+        .filter_map(|level| {
+            let price = level.first()?;
+            let volume = level.get(1)?;
+            Some(Order {
+                price: Decimal::from_str(price).ok()?,
+                quantity: Decimal::from_str(volume).ok()?,
+            })
+        })
+        .collect()
+}
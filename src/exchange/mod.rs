@@ -0,0 +1,79 @@
+// Pluggable exchange feeds.
+//
+// Each venue speaks its own wire format and has its own REST snapshot
+// endpoint, so adapters live in their own submodule and parse independently.
+// They all normalize into `Order`/`BookUpdate`, which is what the rest of
+// the crate (the `OrderBook` and the weighted-average/spread logic) works
+// with, so the same book-maintenance code runs unchanged across venues.
+
+pub mod binance;
+pub mod kraken;
+pub mod okx;
+
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use std::fmt;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+use crate::orderbook::DepthSnapshot;
+
+pub type WsStream = WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>>;
+
+/// A single normalized price level, produced by an adapter after parsing its
+/// venue's own wire format into a common shape.
+#[derive(Debug, Clone, Copy)]
+pub struct Order {
+    pub price: Decimal,
+    pub quantity: Decimal,
+}
+
+/// A normalized diff event: replace these levels in the book.
+///
+/// `first_update_id`/`final_update_id` are only populated by venues (like
+/// Binance) that publish a sequence range per event; venues without one
+/// leave them `None`, and `OrderBook::apply_update` skips the sequence-gap
+/// check for them.
+#[derive(Debug, Default)]
+pub struct BookUpdate {
+    pub bids: Vec<Order>,
+    pub asks: Vec<Order>,
+    pub first_update_id: Option<u64>,
+    pub final_update_id: Option<u64>,
+}
+
+#[derive(Debug)]
+pub enum ExchangeError {
+    Transport(String),
+    Decode(String),
+    /// The venue's own integrity check (e.g. OKX's depth checksum) failed;
+    /// the local book is desynchronized and must be reseeded from a fresh
+    /// snapshot.
+    Desync(String),
+}
+
+impl fmt::Display for ExchangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExchangeError::Transport(msg) => write!(f, "transport error: {msg}"),
+            ExchangeError::Decode(msg) => write!(f, "decode error: {msg}"),
+            ExchangeError::Desync(msg) => write!(f, "book desynchronized: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for ExchangeError {}
+
+/// A venue-specific feed of order book diffs, normalized so the rest of the
+/// crate never has to know which exchange it's talking to.
+#[async_trait]
+pub trait OrderBookSource {
+    /// Open the underlying websocket connection and subscribe to the book.
+    async fn connect(&mut self) -> Result<(), ExchangeError>;
+
+    /// Fetch a full depth snapshot to seed (or reseed, after a resync) the
+    /// book.
+    async fn snapshot(&self) -> Result<DepthSnapshot, ExchangeError>;
+
+    /// Read the next diff event off the wire, or `None` if the stream ended.
+    async fn next_update(&mut self) -> Result<Option<BookUpdate>, ExchangeError>;
+}
@@ -0,0 +1,299 @@
+// OKX `books` channel diff stream, plus its REST order-book endpoint.
+//
+// OKX frames carry four-element levels (`[price, size, liquidated orders,
+// order count]`); only the first two are needed to maintain the book. Each
+// frame also carries a CRC32 checksum of its own top-25 levels, which we
+// mirror locally and verify to catch a desynchronized book.
+
+use async_trait::async_trait;
+use futures_util::{SinkExt, StreamExt};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use tokio_tungstenite::{connect_async, tungstenite::protocol::Message};
+use url::Url;
+
+use super::{BookUpdate, ExchangeError, Order, OrderBookSource, WsStream};
+use crate::orderbook::DepthSnapshot;
+
+const WS_URL: &str = "wss://ws.okx.com:8443/ws/v5/public";
+const CHECKSUM_DEPTH: usize = 25;
+
+pub struct OkxSource {
+    inst_id: String,
+    socket: Option<WsStream>,
+    mirror: Mirror,
+}
+
+impl OkxSource {
+    pub fn new(inst_id: impl Into<String>) -> Self {
+        OkxSource {
+            inst_id: inst_id.into(),
+            socket: None,
+            mirror: Mirror::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl OrderBookSource for OkxSource {
+    async fn connect(&mut self) -> Result<(), ExchangeError> {
+        let url = Url::parse(WS_URL).map_err(|e| ExchangeError::Transport(e.to_string()))?;
+        let (mut socket, _response) = connect_async(url)
+            .await
+            .map_err(|e| ExchangeError::Transport(e.to_string()))?;
+
+        let subscribe = json!({
+            "op": "subscribe",
+            "args": [{"channel": "books", "instId": self.inst_id}],
+        });
+        socket
+            .send(Message::Text(subscribe.to_string()))
+            .await
+            .map_err(|e| ExchangeError::Transport(e.to_string()))?;
+
+        self.socket = Some(socket);
+        Ok(())
+    }
+
+    async fn snapshot(&self) -> Result<DepthSnapshot, ExchangeError> {
+        let url = format!(
+            "https://www.okx.com/api/v5/market/books?instId={}&sz=400",
+            self.inst_id
+        );
+        let response: RestResponse = reqwest::get(&url)
+            .await
+            .map_err(|e| ExchangeError::Transport(e.to_string()))?
+            .json()
+            .await
+            .map_err(|e| ExchangeError::Decode(e.to_string()))?;
+        let book = response
+            .data
+            .into_iter()
+            .next()
+            .ok_or_else(|| ExchangeError::Decode("empty OKX depth response".to_string()))?;
+
+        Ok(DepthSnapshot {
+            last_update_id: 0,
+            bids: parse_levels(&book.bids),
+            asks: parse_levels(&book.asks),
+        })
+    }
+
+    async fn next_update(&mut self) -> Result<Option<BookUpdate>, ExchangeError> {
+        let socket = self
+            .socket
+            .as_mut()
+            .expect("OkxSource::connect must be called before next_update");
+
+        loop {
+            let Some(message) = socket.next().await else {
+                return Ok(None);
+            };
+            let message = message.map_err(|e| ExchangeError::Transport(e.to_string()))?;
+            let text = match message {
+                Message::Text(text) => text,
+                Message::Ping(payload) => {
+                    socket
+                        .send(Message::Pong(payload))
+                        .await
+                        .map_err(|e| ExchangeError::Transport(e.to_string()))?;
+                    continue;
+                }
+                _ => continue,
+            };
+
+            let Ok(frame) = serde_json::from_str::<Frame>(&text) else {
+                // Subscription acks and pongs don't carry a `data` array.
+                continue;
+            };
+            let Some(book) = frame.data.into_iter().next() else {
+                continue;
+            };
+
+            if frame.action == "snapshot" {
+                self.mirror = Mirror::new();
+            }
+            self.mirror.apply(&book);
+
+            let computed = self.mirror.checksum();
+            if computed != book.checksum {
+                return Err(ExchangeError::Desync(format!(
+                    "OKX checksum mismatch for {}: frame {} != computed {computed}",
+                    self.inst_id, book.checksum
+                )));
+            }
+
+            return Ok(Some(BookUpdate {
+                bids: parse_levels(&book.bids),
+                asks: parse_levels(&book.asks),
+                first_update_id: None,
+                final_update_id: None,
+            }));
+        }
+    }
+}
+
+/// A local mirror of OKX's top book levels, kept in the exchange's raw
+/// string representation so the checksum is computed over exactly the bytes
+/// OKX used, not a reformatted `Decimal`.
+struct Mirror {
+    bids: BTreeMap<Decimal, (String, String)>,
+    asks: BTreeMap<Decimal, (String, String)>,
+}
+
+impl Mirror {
+    fn new() -> Self {
+        Mirror {
+            bids: BTreeMap::new(),
+            asks: BTreeMap::new(),
+        }
+    }
+
+    fn apply(&mut self, book: &BookData) {
+        apply_raw_levels(&mut self.bids, &book.bids);
+        apply_raw_levels(&mut self.asks, &book.asks);
+    }
+
+    /// OKX's checksum: interleave `bidPrice:bidSize:askPrice:askSize` for
+    /// the top `CHECKSUM_DEPTH` levels, emitting whichever side still has a
+    /// level once the other runs out (a zip-longest, not a zip), then CRC32
+    /// the resulting string, reinterpreted as `i32`.
+    fn checksum(&self) -> i32 {
+        let bids: Vec<_> = self.bids.iter().rev().take(CHECKSUM_DEPTH).collect();
+        let asks: Vec<_> = self.asks.iter().take(CHECKSUM_DEPTH).collect();
+
+        let mut parts = Vec::new();
+        for i in 0..bids.len().max(asks.len()) {
+            if let Some((_, (bid_price, bid_size))) = bids.get(i) {
+                parts.push(bid_price.as_str());
+                parts.push(bid_size.as_str());
+            }
+            if let Some((_, (ask_price, ask_size))) = asks.get(i) {
+                parts.push(ask_price.as_str());
+                parts.push(ask_size.as_str());
+            }
+        }
+        let joined = parts.join(":");
+
+        crc32fast::hash(joined.as_bytes()) as i32
+    }
+}
+
+fn apply_raw_levels(side: &mut BTreeMap<Decimal, (String, String)>, levels: &[[String; 4]]) {
+    for [price, size, _, _] in levels {
+        let Ok(price_dec) = Decimal::from_str(price) else {
+            continue;
+        };
+        let Ok(size_dec) = Decimal::from_str(size) else {
+            continue;
+        };
+
+        if size_dec.is_zero() {
+            side.remove(&price_dec);
+        } else {
+            side.insert(price_dec, (price.clone(), size.clone()));
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct Frame {
+    action: String,
+    data: Vec<BookData>,
+}
+
+#[derive(Deserialize, Debug)]
+struct BookData {
+    asks: Vec<[String; 4]>,
+    bids: Vec<[String; 4]>,
+    checksum: i32,
+}
+
+#[derive(Deserialize, Debug)]
+struct RestResponse {
+    data: Vec<RestBookData>,
+}
+
+#[derive(Deserialize, Debug)]
+struct RestBookData {
+    asks: Vec<[String; 4]>,
+    bids: Vec<[String; 4]>,
+}
+
+fn parse_levels(levels: &[[String; 4]]) -> Vec<Order> {
+    levels
+        .iter()
+        .filter_map(|[price, size, _, _]| {
+            Some(Order {
+                price: Decimal::from_str(price).ok()?,
+                quantity: Decimal::from_str(size).ok()?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn level(price: &str, size: &str) -> [String; 4] {
+        [
+            price.to_string(),
+            size.to_string(),
+            "0".to_string(),
+            "1".to_string(),
+        ]
+    }
+
+    fn book_data(bids: Vec<[String; 4]>, asks: Vec<[String; 4]>, checksum: i32) -> BookData {
+        BookData {
+            bids,
+            asks,
+            checksum,
+        }
+    }
+
+    // Known-good vector: CRC32 of "100.5:1.0:101.5:2.0", reinterpreted as
+    // i32, computed independently with Python's `zlib.crc32`.
+    #[test]
+    fn checksum_matches_equal_depth_vector() {
+        let mut mirror = Mirror::new();
+        mirror.apply(&book_data(
+            vec![level("100.5", "1.0")],
+            vec![level("101.5", "2.0")],
+            0,
+        ));
+
+        assert_eq!(mirror.checksum(), -868202363);
+    }
+
+    // Known-good vector: CRC32 of "100.5:1.0:101.5:2.0:100.0:0.5" — the
+    // extra bid level is still included once the ask side runs out.
+    #[test]
+    fn checksum_uses_zip_longest_when_depths_differ() {
+        let mut mirror = Mirror::new();
+        mirror.apply(&book_data(
+            vec![level("100.5", "1.0"), level("100.0", "0.5")],
+            vec![level("101.5", "2.0")],
+            0,
+        ));
+
+        assert_eq!(mirror.checksum(), -1254629141);
+    }
+
+    #[test]
+    fn zero_size_level_is_removed_from_the_mirror() {
+        let mut mirror = Mirror::new();
+        mirror.apply(&book_data(
+            vec![level("100.5", "1.0")],
+            vec![level("101.5", "2.0")],
+            0,
+        ));
+        mirror.apply(&book_data(vec![level("100.5", "0")], vec![], 0));
+
+        assert!(mirror.bids.is_empty());
+    }
+}
@@ -1,121 +1,217 @@
 // Import necessary libraries
-use futures_util::stream::StreamExt;
-use rust_decimal::prelude::ToPrimitive;
+use clap::Parser;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
 use rust_decimal::Decimal;
-use serde::{Deserialize, Serialize};
-use std::cmp::Ordering;
-use std::cmp::Reverse;
-use std::collections::BinaryHeap;
-use std::str::FromStr;
-
-use tokio_tungstenite::{connect_async, tungstenite::protocol::Message};
-use url::Url;
-
-#[derive(Serialize, Deserialize, Debug)]
-struct WebSocketMessage {
-    e: String,           // Event type
-    E: u64,              // Event time
-    s: String,           // Symbol
-    U: u64,              // First update ID in event
-    u: u64,              // Final update ID in event
-    b: Vec<[String; 2]>, // Bids to be updated
-    a: Vec<[String; 2]>, // Asks to be updated
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use tokio::sync::RwLock;
+
+mod api;
+mod backoff;
+mod exchange;
+mod orderbook;
+mod quote;
+
+use backoff::Backoff;
+use exchange::binance::BinanceSource;
+use exchange::kraken::KrakenSource;
+use exchange::okx::OkxSource;
+use exchange::{ExchangeError, OrderBookSource};
+use orderbook::{
+    calculate_weighted_average_price_asks, calculate_weighted_average_price_bids, OrderBook,
+    SyncError,
+};
+use quote::Rate;
+
+const HTTP_ADDR: &str = "127.0.0.1:8080";
+
+/// Which venue's `OrderBookSource` adapter to run.
+#[derive(Copy, Clone, Debug, clap::ValueEnum)]
+enum ExchangeKind {
+    Binance,
+    Okx,
+    Kraken,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-struct Order {
-    price: Decimal,
-    quantity: Decimal,
-}
+#[derive(Parser, Debug)]
+#[command(author, version, about)]
+struct Args {
+    /// Spread applied outward to the quoted ask price, e.g. 0.02 = 2%.
+    #[arg(long, default_value_t = 0.02)]
+    ask_spread: f64,
 
-impl Ord for Order {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.price.partial_cmp(&other.price).unwrap()
-    }
+    /// Which exchange feed to run.
+    #[arg(long, value_enum, default_value_t = ExchangeKind::Binance)]
+    exchange: ExchangeKind,
 }
 
-impl PartialOrd for Order {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-struct OrderBook {
-    bids: BinaryHeap<Order>,
-    asks: BinaryHeap<Reverse<Order>>, // Min-heap for asks
-}
-
-impl OrderBook {
-    fn new() -> Self {
-        OrderBook {
-            bids: BinaryHeap::new(),
-            asks: BinaryHeap::new(),
-        }
-    }
-
-    // Function to add orders to the order book
-    fn add_order(&mut self, order: Order, order_type: &str) {
-        match order_type {
-            "bid" => self.bids.push(order),
-            "ask" => self.asks.push(Reverse(order)),
-            _ => panic!("Unknown order type"),
-        }
+/// Build the adapter for the chosen venue, along with the instrument symbol
+/// it's subscribed to (each venue names it differently).
+fn make_source(kind: ExchangeKind) -> (Box<dyn OrderBookSource>, String) {
+    match kind {
+        ExchangeKind::Binance => (
+            Box::new(BinanceSource::new("btcusdt")),
+            "btcusdt".to_string(),
+        ),
+        ExchangeKind::Okx => (Box::new(OkxSource::new("BTC-USDT")), "BTC-USDT".to_string()),
+        ExchangeKind::Kraken => (
+            Box::new(KrakenSource::new("XBT/USDT")),
+            "XBT/USDT".to_string(),
+        ),
     }
 }
 
 #[tokio::main]
 async fn main() {
-    // Define the WebSocket URL for the Binance order book stream
-    let url = Url::parse("wss://stream.binance.com:9443/ws/btcusdt@depth").unwrap();
+    let args = Args::parse();
+    let ask_spread = Decimal::from_f64(args.ask_spread).unwrap_or(Decimal::ZERO);
+    let (mut source, symbol) = make_source(args.exchange);
 
-    // Connect to the WebSocket
-    let (mut socket, response) = connect_async(url).await.expect("Failed to connect");
+    let mut backoff = Backoff::default();
+    reconnect(source.as_mut(), &mut backoff).await;
 
     println!("Connected to the server");
-    println!("Response HTTP code: {}", response.status());
-    println!("Response contains the following headers:");
-    for (ref header, _value) in response.headers() {
-        println!("{}", header);
-    }
 
-    // Listen for messages
-    while let Some(message) = socket.next().await {
-        let message = message.unwrap();
+    let order_book = sync_order_book(source.as_mut(), &mut backoff).await;
+    let book = Arc::new(RwLock::new(order_book));
+
+    let state = api::AppState {
+        symbol: symbol.clone(),
+        ask_spread,
+        book: book.clone(),
+    };
+    let listener = TcpListener::bind(HTTP_ADDR)
+        .await
+        .expect("failed to bind HTTP listener");
+    println!("Serving HTTP API on http://{HTTP_ADDR}");
+    let server = tokio::spawn(async move {
+        axum::serve(listener, api::router(state))
+            .await
+            .expect("HTTP server failed");
+    });
+
+    run_feed(source.as_mut(), book, ask_spread, backoff).await;
+
+    let _ = server.await;
+}
 
-        // Handle different types of messages here
-        match message {
-            Message::Text(text) => handle_message(text),
-            Message::Binary(bin) => println!("Received binary data: {:?}", bin),
-            _ => (),
+/// Drive the websocket feed, applying each update to the shared book,
+/// resyncing from a fresh snapshot whenever a sequence gap or checksum
+/// mismatch is detected, and reconnecting with backoff whenever the
+/// connection itself drops so a long-running watcher survives network
+/// blips rather than exiting.
+async fn run_feed(
+    source: &mut dyn OrderBookSource,
+    book: Arc<RwLock<OrderBook>>,
+    ask_spread: Decimal,
+    mut backoff: Backoff,
+) {
+    loop {
+        match source.next_update().await {
+            Ok(Some(update)) => {
+                let mut guard = book.write().await;
+                match guard.apply_update(&update) {
+                    Ok(()) => print_quote(&guard, ask_spread),
+                    Err(SyncError::Stale) => {}
+                    Err(SyncError::Gap) => {
+                        drop(guard);
+                        println!("sequence gap detected, resyncing from snapshot");
+                        let fresh = sync_order_book(source, &mut backoff).await;
+                        *book.write().await = fresh;
+                    }
+                }
+            }
+            Ok(None) => {
+                println!("stream ended, reconnecting");
+                reconnect(source, &mut backoff).await;
+                let fresh = sync_order_book(source, &mut backoff).await;
+                *book.write().await = fresh;
+            }
+            Err(ExchangeError::Desync(reason)) => {
+                // A venue-side integrity check failed (e.g. OKX's checksum),
+                // so any local mirror of the feed is corrupt too. Fetching a
+                // fresh REST snapshot alone isn't enough: the adapter also
+                // needs to resubscribe so the venue emits a new initial
+                // frame and clears the corrupt state behind that checksum.
+                println!("{reason}, reconnecting and resyncing from snapshot");
+                reconnect(source, &mut backoff).await;
+                let fresh = sync_order_book(source, &mut backoff).await;
+                *book.write().await = fresh;
+            }
+            Err(ExchangeError::Transport(reason)) => {
+                println!("{reason}, reconnecting");
+                reconnect(source, &mut backoff).await;
+                let fresh = sync_order_book(source, &mut backoff).await;
+                *book.write().await = fresh;
+            }
+            Err(err) => {
+                eprintln!("stream error: {err}");
+            }
         }
     }
 }
 
-fn handle_message(text: String) {
-    // Parse the message text and update order book data
-
-    let mut order_book = OrderBook::new();
-
-    let message: WebSocketMessage = serde_json::from_str(&text).unwrap();
-
-    for bid in message.b {
-        let price = Decimal::from_str(&bid[0]).unwrap();
-        let quantity = Decimal::from_str(&bid[1]).unwrap();
-
-        // Add or update the bid in your order book
-        order_book.add_order(Order { price, quantity }, "bid");
+/// Reconnect with exponential backoff, retrying indefinitely since a
+/// long-running watcher should outlast any single network blip.
+async fn reconnect(source: &mut dyn OrderBookSource, backoff: &mut Backoff) {
+    loop {
+        match source.connect().await {
+            Ok(()) => {
+                backoff.reset();
+                return;
+            }
+            Err(err) => {
+                let delay = backoff.next_delay();
+                eprintln!("reconnect failed: {err}, retrying in {delay:?}");
+                tokio::time::sleep(delay).await;
+            }
+        }
     }
+}
 
-    for ask in message.a {
-        let price = Decimal::from_str(&ask[0]).unwrap();
-        let quantity = Decimal::from_str(&ask[1]).unwrap();
-
-        // Add or update the ask in your order book
-        order_book.add_order(Order { price, quantity }, "ask");
+/// Seed a fresh `OrderBook` from a REST snapshot, then drain already-buffered
+/// updates until one straddles the snapshot (for venues that publish a
+/// sequence range), retrying the whole snapshot fetch if a gap is found
+/// first.
+async fn sync_order_book(source: &mut dyn OrderBookSource, backoff: &mut Backoff) -> OrderBook {
+    loop {
+        let snapshot = loop {
+            match source.snapshot().await {
+                Ok(snapshot) => break snapshot,
+                Err(err) => eprintln!("failed to fetch depth snapshot: {err}, retrying"),
+            }
+        };
+        let mut book = OrderBook::from_snapshot(snapshot);
+
+        loop {
+            match source.next_update().await {
+                Ok(Some(update)) => match book.apply_update(&update) {
+                    Ok(()) => return book,
+                    Err(SyncError::Stale) => continue,
+                    Err(SyncError::Gap) => break,
+                },
+                Ok(None) => return book,
+                Err(ExchangeError::Desync(reason)) => {
+                    // Reconnect so the venue resends an initial frame (and,
+                    // for OKX, clears the corrupt checksum mirror) before
+                    // retrying the snapshot fetch; otherwise every update
+                    // would keep failing the same integrity check.
+                    eprintln!("{reason}, reconnecting and retrying sync");
+                    reconnect(source, backoff).await;
+                    break;
+                }
+                Err(err) => {
+                    eprintln!("stream error while syncing: {err}");
+                    return book;
+                }
+            }
+        }
     }
+}
 
-    // Calculate the weighted average price for the first 10 bids and asks
-    let depth = Decimal::from_str("1").unwrap(); // Example depth
+fn print_quote(order_book: &OrderBook, ask_spread: Decimal) {
+    // Calculate the weighted average price for the first unit of depth.
+    let depth = Decimal::from(1);
 
     let bid_avg_price =
         calculate_weighted_average_price_bids(&order_book.bids, depth).unwrap_or(Decimal::ZERO);
@@ -123,69 +219,14 @@ fn handle_message(text: String) {
     let ask_avg_price =
         calculate_weighted_average_price_asks(&order_book.asks, depth).unwrap_or(Decimal::ZERO);
 
-    let spread = ask_avg_price - bid_avg_price;
+    let ask_quote = Rate::new(ask_avg_price, ask_spread);
+    let spread = ask_quote.quoted() - bid_avg_price;
 
-    // Format and print the values
-    // Convert Decimal to a primitive float for easy formatting (consider precision needs)
     println!(
-        "Best Bid: {:.2}, Best Ask: {:.2}, Spread: {:.2}",
+        "Best Bid: {:.2}, Best Ask: {:.2} (raw {:.2}), Spread: {:.2}",
         bid_avg_price.to_f64().unwrap_or(0.0),
-        ask_avg_price.to_f64().unwrap_or(0.0),
+        ask_quote.quoted().to_f64().unwrap_or(0.0),
+        ask_quote.raw().to_f64().unwrap_or(0.0),
         spread.to_f64().unwrap_or(0.0)
     );
 }
-
-// Calculate Weighted Average Price for Bids
-fn calculate_weighted_average_price_bids(
-    bids: &BinaryHeap<Order>,
-    depth: Decimal,
-) -> Option<Decimal> {
-    let mut weighted_sum = Decimal::ZERO;
-    let mut total_quantity = Decimal::ZERO;
-
-    for order in bids.iter() {
-        if total_quantity + order.quantity >= depth {
-            let remaining_quantity = depth - total_quantity;
-            weighted_sum += order.price * remaining_quantity;
-            total_quantity += remaining_quantity;
-            break;
-        } else {
-            weighted_sum += order.price * order.quantity;
-            total_quantity += order.quantity;
-        }
-    }
-
-    if total_quantity.is_zero() {
-        None
-    } else {
-        Some(weighted_sum / total_quantity)
-    }
-}
-
-// Calculate Weighted Average Price for Asks
-fn calculate_weighted_average_price_asks(
-    asks: &BinaryHeap<Reverse<Order>>,
-    depth: Decimal,
-) -> Option<Decimal> {
-    let mut weighted_sum = Decimal::ZERO;
-    let mut total_quantity = Decimal::ZERO;
-
-    for reverse_order in asks.iter() {
-        let order = &reverse_order.0; // Extract the Order from Reverse<Order>
-        if total_quantity + order.quantity >= depth {
-            let remaining_quantity = depth - total_quantity;
-            weighted_sum += order.price * remaining_quantity;
-            total_quantity += remaining_quantity;
-            break;
-        } else {
-            weighted_sum += order.price * order.quantity;
-            total_quantity += order.quantity;
-        }
-    }
-
-    if total_quantity.is_zero() {
-        None
-    } else {
-        Some(weighted_sum / total_quantity)
-    }
-}
@@ -0,0 +1,240 @@
+// Stateful, diff-maintained order book.
+//
+// Exchange feeds publish incremental diffs rather than full snapshots, so
+// the book has to be seeded from a REST snapshot and then kept in sync by
+// applying each diff in order, watching for gaps in the update-id sequence
+// where the venue provides one.
+
+use rust_decimal::Decimal;
+use std::collections::BTreeMap;
+
+use crate::exchange::{BookUpdate, Order};
+
+/// A full depth snapshot as returned by an exchange's REST endpoint.
+#[derive(Debug, Clone)]
+pub struct DepthSnapshot {
+    pub last_update_id: u64,
+    pub bids: Vec<Order>,
+    pub asks: Vec<Order>,
+}
+
+/// Raised when an incoming diff can't be applied to the current book state.
+#[derive(Debug)]
+pub enum SyncError {
+    /// The event is entirely older than what we already applied.
+    Stale,
+    /// There's a hole between the last applied id and this event's first id;
+    /// the book must be reseeded from a fresh snapshot.
+    Gap,
+}
+
+/// Price-indexed order book, kept current by applying exchange diffs on top
+/// of a REST snapshot.
+pub struct OrderBook {
+    pub bids: BTreeMap<Decimal, Decimal>,
+    pub asks: BTreeMap<Decimal, Decimal>,
+    pub last_update_id: u64,
+}
+
+impl OrderBook {
+    /// Seed the book from a REST depth snapshot. No diffs have been applied
+    /// yet, so the first one received must straddle `last_update_id + 1`.
+    pub fn from_snapshot(snapshot: DepthSnapshot) -> Self {
+        let mut book = OrderBook {
+            bids: BTreeMap::new(),
+            asks: BTreeMap::new(),
+            last_update_id: snapshot.last_update_id,
+        };
+        apply_levels(&mut book.bids, &snapshot.bids);
+        apply_levels(&mut book.asks, &snapshot.asks);
+        book
+    }
+
+    /// Apply a single diff event. Venues that publish an update-id range
+    /// (currently Binance) are checked against the sequencing rules: drop
+    /// anything older than our snapshot, require the event to straddle
+    /// `last_update_id + 1` (which also covers the very first event applied
+    /// after a snapshot), and report a gap otherwise so the caller can
+    /// resync from a fresh snapshot. Venues that don't publish update ids
+    /// leave them `None` and the sequence check is skipped.
+    pub fn apply_update(&mut self, update: &BookUpdate) -> Result<(), SyncError> {
+        if let (Some(first_update_id), Some(final_update_id)) =
+            (update.first_update_id, update.final_update_id)
+        {
+            if final_update_id <= self.last_update_id {
+                return Err(SyncError::Stale);
+            }
+            if first_update_id > self.last_update_id + 1 {
+                return Err(SyncError::Gap);
+            }
+            self.last_update_id = final_update_id;
+        }
+
+        apply_levels(&mut self.bids, &update.bids);
+        apply_levels(&mut self.asks, &update.asks);
+        Ok(())
+    }
+}
+
+/// Apply a batch of levels to a side of the book, replacing the quantity at
+/// each price and removing the level entirely when the quantity drops to
+/// zero.
+fn apply_levels(side: &mut BTreeMap<Decimal, Decimal>, levels: &[Order]) {
+    for level in levels {
+        if level.quantity.is_zero() {
+            side.remove(&level.price);
+        } else {
+            side.insert(level.price, level.quantity);
+        }
+    }
+}
+
+/// Weighted average price for filling `depth` worth of quantity against the
+/// bid side, walking from the best (highest) bid down.
+pub fn calculate_weighted_average_price_bids(
+    bids: &BTreeMap<Decimal, Decimal>,
+    depth: Decimal,
+) -> Option<Decimal> {
+    weighted_average(bids.iter().rev(), depth)
+}
+
+/// Weighted average price for filling `depth` worth of quantity against the
+/// ask side, walking from the best (lowest) ask up.
+pub fn calculate_weighted_average_price_asks(
+    asks: &BTreeMap<Decimal, Decimal>,
+    depth: Decimal,
+) -> Option<Decimal> {
+    weighted_average(asks.iter(), depth)
+}
+
+fn weighted_average<'a>(
+    levels: impl Iterator<Item = (&'a Decimal, &'a Decimal)>,
+    depth: Decimal,
+) -> Option<Decimal> {
+    let mut weighted_sum = Decimal::ZERO;
+    let mut total_quantity = Decimal::ZERO;
+
+    for (price, quantity) in levels {
+        if total_quantity + quantity >= depth {
+            let remaining_quantity = depth - total_quantity;
+            weighted_sum += price * remaining_quantity;
+            total_quantity += remaining_quantity;
+            break;
+        } else {
+            weighted_sum += price * quantity;
+            total_quantity += quantity;
+        }
+    }
+
+    if total_quantity.is_zero() {
+        None
+    } else {
+        Some(weighted_sum / total_quantity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exchange::Order;
+
+    fn order(price: &str, quantity: &str) -> Order {
+        Order {
+            price: price.parse().unwrap(),
+            quantity: quantity.parse().unwrap(),
+        }
+    }
+
+    fn update(first: u64, last: u64) -> BookUpdate {
+        BookUpdate {
+            bids: vec![order("99", "1")],
+            asks: vec![order("101", "1")],
+            first_update_id: Some(first),
+            final_update_id: Some(last),
+        }
+    }
+
+    fn snapshot(last_update_id: u64) -> DepthSnapshot {
+        DepthSnapshot {
+            last_update_id,
+            bids: vec![order("100", "1")],
+            asks: vec![order("102", "1")],
+        }
+    }
+
+    #[test]
+    fn first_event_straddling_the_snapshot_is_applied() {
+        let mut book = OrderBook::from_snapshot(snapshot(10));
+        assert!(book.apply_update(&update(8, 11)).is_ok());
+        assert_eq!(book.last_update_id, 11);
+    }
+
+    #[test]
+    fn event_entirely_before_the_snapshot_is_stale() {
+        let mut book = OrderBook::from_snapshot(snapshot(10));
+        assert!(matches!(
+            book.apply_update(&update(5, 10)),
+            Err(SyncError::Stale)
+        ));
+        // A stale event must not advance the book.
+        assert_eq!(book.last_update_id, 10);
+    }
+
+    #[test]
+    fn hole_before_the_next_expected_id_is_a_gap() {
+        let mut book = OrderBook::from_snapshot(snapshot(10));
+        assert!(matches!(
+            book.apply_update(&update(12, 15)),
+            Err(SyncError::Gap)
+        ));
+        assert_eq!(book.last_update_id, 10);
+    }
+
+    #[test]
+    fn updates_without_ids_skip_the_sequence_check() {
+        let mut book = OrderBook::from_snapshot(snapshot(10));
+        let update = BookUpdate {
+            bids: vec![order("99", "1")],
+            asks: vec![],
+            first_update_id: None,
+            final_update_id: None,
+        };
+        assert!(book.apply_update(&update).is_ok());
+        assert_eq!(book.last_update_id, 10);
+    }
+
+    #[test]
+    fn zero_quantity_removes_the_level() {
+        let mut book = OrderBook::from_snapshot(snapshot(10));
+        assert!(book.bids.contains_key(&"100".parse().unwrap()));
+
+        let update = BookUpdate {
+            bids: vec![order("100", "0")],
+            asks: vec![],
+            first_update_id: None,
+            final_update_id: None,
+        };
+        book.apply_update(&update).unwrap();
+        assert!(!book.bids.contains_key(&"100".parse().unwrap()));
+    }
+
+    #[test]
+    fn weighted_average_splits_the_level_that_straddles_depth() {
+        let mut bids = BTreeMap::new();
+        bids.insert("10".parse().unwrap(), "1".parse().unwrap());
+        bids.insert("9".parse().unwrap(), "2".parse().unwrap());
+
+        // 1 unit at 10 + 1 unit at 9 = 19 / 2.
+        let avg = calculate_weighted_average_price_bids(&bids, "2".parse().unwrap()).unwrap();
+        assert_eq!(avg, "9.5".parse().unwrap());
+    }
+
+    #[test]
+    fn weighted_average_is_none_for_an_empty_book() {
+        let bids: BTreeMap<Decimal, Decimal> = BTreeMap::new();
+        assert_eq!(
+            calculate_weighted_average_price_bids(&bids, Decimal::ONE),
+            None
+        );
+    }
+}
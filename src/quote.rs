@@ -0,0 +1,31 @@
+// A simple automated-market-maker style quote: take the exchange's raw
+// weighted-average price and push it outward by a configurable spread,
+// following the ASB "ask-spread" idea.
+
+use rust_decimal::Decimal;
+
+/// A quoted price derived from a raw exchange price and a spread applied
+/// multiplicatively on top of it. The raw price stays accessible for
+/// diagnostics even once a spread has been applied.
+#[derive(Debug, Clone, Copy)]
+pub struct Rate {
+    raw: Decimal,
+    spread: Decimal,
+}
+
+impl Rate {
+    pub fn new(raw: Decimal, spread: Decimal) -> Self {
+        Rate { raw, spread }
+    }
+
+    /// The unmodified price as computed from the order book.
+    pub fn raw(&self) -> Decimal {
+        self.raw
+    }
+
+    /// The raw price adjusted outward by the configured spread, e.g. for a
+    /// spread of `0.02`, `raw * 1.02`.
+    pub fn quoted(&self) -> Decimal {
+        self.raw * (Decimal::ONE + self.spread)
+    }
+}